@@ -0,0 +1,153 @@
+// Copyright 2021 Databricks, Inc.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use clap::{Arg, Command as ClapCommand};
+use rustyline::completion::Pair as RustlinePair;
+
+use crate::{
+    command::command_def::{exec_match, start_clap, Cmd},
+    command::command_exec::KubectlCommand,
+    completer,
+    env::Env,
+    error::ClickError,
+    kobj::KObj,
+    output::ClickWriter,
+};
+
+use std::io::Write;
+
+#[allow(clippy::too_many_arguments)]
+fn do_attach(
+    env: &Env,
+    pod: &KObj,
+    kluster_name: &str,
+    it_arg: &Option<&str>,
+    cont_opt: &Option<&str>,
+    term_opt: &Option<&str>,
+    do_terminal: bool,
+    writer: &mut ClickWriter,
+) -> Result<(), ClickError> {
+    let ns = pod.namespace.as_ref().unwrap();
+    let mut kubectl = KubectlCommand::new(env, ns, kluster_name, "attach");
+    if let Some(it) = it_arg {
+        kubectl = kubectl.arg(it);
+    }
+    kubectl = kubectl.arg(pod.name());
+    if let Some(cont) = cont_opt {
+        kubectl = kubectl.arg("-c").arg(cont);
+    }
+
+    if do_terminal {
+        let terminal = if let Some(t) = term_opt {
+            t
+        } else if let Some(ref t) = env.click_config.terminal {
+            t
+        } else {
+            "xterm -e"
+        };
+        clickwriteln!(writer, "Attaching to {} in terminal", pod.name());
+        kubectl.spawn_in_terminal(terminal)
+    } else {
+        kubectl.run()
+    }
+}
+
+command!(
+    Attach,
+    "attach",
+    "attach to the main process of the active pod",
+    |clap: ClapCommand<'static>| clap
+        .arg(
+            Arg::new("container")
+                .short('c')
+                .long("container")
+                .help("Attach to the specified container")
+                .takes_value(true)
+        )
+        .arg(
+            Arg::new("terminal")
+                .short('t')
+                .long("terminal")
+                .help(
+                    "Run the attach in a new terminal.  With --terminal ARG, ARG is used as the \
+                     terminal command, otherwise the default is used ('set terminal <value>' to \
+                     specify default). If a range of objects is selected, a new terminal is opened \
+                     for each object."
+                )
+                .takes_value(true)
+                .min_values(0)
+        )
+        .arg(
+            Arg::new("tty")
+                .short('T')
+                .long("tty")
+                .help("If stdin is a TTY. Contrary to kubectl, this defaults to TRUE")
+                .value_parser(clap::value_parser!(bool))
+                .takes_value(true)
+                .min_values(0)
+        )
+        .arg(
+            Arg::new("stdin")
+                .short('i')
+                .long("stdin")
+                .help("Pass stdin to the container. Contrary to kubectl, this defaults to TRUE")
+                .value_parser(clap::value_parser!(bool))
+                .takes_value(true)
+                .min_values(0)
+        ),
+    vec!["attach"],
+    noop_complete!(),
+    [(
+        "container".to_string(),
+        completer::container_completer as fn(&str, &Env) -> Vec<RustlinePair>
+    )]
+    .into_iter()
+    .collect(),
+    |matches, env, writer| {
+        let context = env.context.as_ref().ok_or_else(|| {
+            ClickError::CommandError("Need an active context in order to attach.".to_string())
+        })?;
+        let tty = !matches.contains_id("tty") || *matches.get_one::<bool>("tty").unwrap();
+        let stdin = !matches.contains_id("stdin") || *matches.get_one::<bool>("stdin").unwrap();
+        let it_arg = match (tty, stdin) {
+            (true, true) => Some("-it"),
+            (true, false) => Some("-t"),
+            (false, true) => Some("-i"),
+            (false, false) => None,
+        };
+        env.apply_to_selection(
+            writer,
+            Some(&env.click_config.range_separator),
+            |obj, writer| {
+                if obj.is_pod() {
+                    do_attach(
+                        env,
+                        obj,
+                        &context.name,
+                        &it_arg,
+                        &matches.get_one::<String>("container").map(|s| s.as_str()),
+                        &matches.get_one::<String>("terminal").map(|s| s.as_str()),
+                        matches.contains_id("terminal"),
+                        writer,
+                    )
+                } else {
+                    Err(ClickError::CommandError(
+                        "Attach only possible on pods".to_string(),
+                    ))
+                }
+            },
+        )
+    },
+    false
+);
@@ -0,0 +1,132 @@
+// Copyright 2021 Databricks, Inc.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A shared builder for commands that shell out to `kubectl`, such as `exec`,
+//! `attach`, and `port-forward`.  Centralizes binary resolution, the standard
+//! `--namespace`/`--context`/`--as` flags, and the "could not find kubectl
+//! binary" error mapping so each command only has to describe its own
+//! subcommand and arguments.
+
+use crate::{env::Env, error::ClickError};
+
+use std::io;
+use std::process::Command;
+
+/// Builds and runs a single `kubectl` invocation, either in the foreground or
+/// detached in a terminal.
+pub struct KubectlCommand<'a> {
+    binary: &'a str,
+    namespace: &'a str,
+    context: &'a str,
+    impersonate: Option<&'a str>,
+    subcommand: &'a str,
+    args: Vec<&'a str>,
+}
+
+impl<'a> KubectlCommand<'a> {
+    pub fn new(
+        env: &'a Env,
+        namespace: &'a str,
+        context: &'a str,
+        subcommand: &'a str,
+    ) -> KubectlCommand<'a> {
+        KubectlCommand {
+            binary: env
+                .click_config
+                .kubectl_binary
+                .as_deref()
+                .unwrap_or("kubectl"),
+            namespace,
+            context,
+            impersonate: env.get_impersonate_user(),
+            subcommand,
+            args: Vec::new(),
+        }
+    }
+
+    /// Append a single argument, to be passed after `--namespace`/`--context`,
+    /// the subcommand and `--as` (when impersonating).
+    pub fn arg(mut self, arg: &'a str) -> Self {
+        self.args.push(arg);
+        self
+    }
+
+    /// Append several arguments at once.
+    pub fn args<I: IntoIterator<Item = &'a str>>(mut self, args: I) -> Self {
+        self.args.extend(args);
+        self
+    }
+
+    fn full_args(&self) -> Vec<&'a str> {
+        let mut full = vec![
+            "--namespace",
+            self.namespace,
+            "--context",
+            self.context,
+            self.subcommand,
+        ];
+        if let Some(user) = self.impersonate {
+            full.push("--as");
+            full.push(user);
+        }
+        full.extend(self.args.iter().copied());
+        full
+    }
+
+    /// Run `kubectl` in the foreground, waiting for it to exit.
+    pub fn run(&self) -> Result<(), ClickError> {
+        let mut command = Command::new(self.binary);
+        command.args(self.full_args());
+        match command.status() {
+            Ok(s) => {
+                if s.success() {
+                    Ok(())
+                } else {
+                    Err(ClickError::CommandError(
+                        "kubectl exited abnormally".to_string(),
+                    ))
+                }
+            }
+            Err(e) => self.map_spawn_error(e),
+        }
+    }
+
+    /// Run `kubectl` detached, inside `terminal_spec` (e.g. `"xterm -e"`).
+    pub fn spawn_in_terminal(&self, terminal_spec: &'a str) -> Result<(), ClickError> {
+        let mut targs: Vec<&str> = terminal_spec.split_whitespace().collect();
+        targs.push(self.binary);
+        targs.extend(self.full_args());
+        duct::cmd(targs[0], &targs[1..]).start()?;
+        Ok(())
+    }
+
+    fn map_spawn_error(&self, e: io::Error) -> Result<(), ClickError> {
+        if let io::ErrorKind::NotFound = e.kind() {
+            let msg = if self.binary.starts_with('/') {
+                format!(
+                    "Could not find kubectl binary: '{}'. Does it exist?",
+                    self.binary
+                )
+            } else {
+                format!(
+                    "Could not find kubectl binary: '{}'. Is it in your PATH?",
+                    self.binary
+                )
+            };
+            Err(ClickError::CommandError(msg))
+        } else {
+            Err(ClickError::Io(e))
+        }
+    }
+}
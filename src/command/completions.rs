@@ -0,0 +1,59 @@
+// Copyright 2021 Databricks, Inc.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use clap::{Arg, ArgEnum, Command as ClapCommand};
+use clap_complete::Shell;
+
+use crate::{
+    command::command_def::{exec_match, start_clap, Cmd},
+    env::Env,
+    error::ClickError,
+    output::ClickWriter,
+};
+
+use std::collections::HashMap;
+use std::io::Write;
+
+command!(
+    Completions,
+    "completions",
+    "generate a shell completion script for click's own commands",
+    |clap: ClapCommand<'static>| clap.arg(
+        Arg::new("shell")
+            .help("The shell to generate completions for")
+            .required(true)
+            .possible_values(Shell::value_variants().iter().map(|s| s.to_possible_value().unwrap()))
+            .index(1)
+    ),
+    vec!["completions"],
+    noop_complete!(),
+    HashMap::new(),
+    |matches, _env, writer| {
+        let shell = matches
+            .get_one::<String>("shell")
+            .map(|s| s.parse::<Shell>())
+            .unwrap() // safe as required
+            .map_err(|e| ClickError::CommandError(format!("Unknown shell: {e}")))?;
+        let mut app = ClapCommand::new("click");
+        // Plugins aren't included here since this series doesn't plumb a
+        // plugin directory setting through to this command; only the
+        // built-in commands get completions.
+        for cmd in crate::command::command_vec(None) {
+            app = app.subcommand(cmd.clap_command());
+        }
+        clap_complete::generate(shell, &mut app, "click", writer);
+        Ok(())
+    },
+    false
+);
@@ -17,6 +17,7 @@ use rustyline::completion::Pair as RustlinePair;
 
 use crate::{
     command::command_def::{exec_match, start_clap, Cmd},
+    command::command_exec::KubectlCommand,
     completer,
     env::Env,
     error::ClickError,
@@ -26,8 +27,7 @@ use crate::{
 
 use std::cell::RefCell;
 use std::collections::HashMap;
-use std::io::{self, Write};
-use std::process::Command;
+use std::io::Write;
 
 #[allow(clippy::too_many_arguments)]
 fn do_exec(
@@ -42,11 +42,16 @@ fn do_exec(
     writer: &mut ClickWriter,
 ) -> Result<(), ClickError> {
     let ns = pod.namespace.as_ref().unwrap();
-    let kubectl_binary = env
-        .click_config
-        .kubectl_binary
-        .as_deref()
-        .unwrap_or("kubectl");
+    let mut kubectl = KubectlCommand::new(env, ns, kluster_name, "exec");
+    if let Some(it) = it_arg {
+        kubectl = kubectl.arg(it);
+    }
+    kubectl = kubectl.arg(pod.name());
+    if let Some(cont) = cont_opt {
+        kubectl = kubectl.arg("-c").arg(cont);
+    }
+    kubectl = kubectl.arg("--").args(cmd.iter().copied());
+
     if do_terminal {
         let terminal = if let Some(t) = term_opt {
             t
@@ -55,78 +60,10 @@ fn do_exec(
         } else {
             "xterm -e"
         };
-        let mut targs: Vec<&str> = terminal.split_whitespace().collect();
-        let mut kubectl_args = vec![
-            kubectl_binary,
-            "--namespace",
-            ns,
-            "--context",
-            kluster_name,
-            "exec",
-        ];
-        targs.append(&mut kubectl_args);
-        if let Some(it) = it_arg {
-            targs.push(it);
-        }
-        targs.push(pod.name());
-        if let Some(cont) = cont_opt {
-            targs.push("-c");
-            targs.push(cont);
-        }
-        if let Some(user) = env.get_impersonate_user() {
-            targs.push("--as");
-            targs.push(user);
-        }
-        targs.push("--");
-        targs.extend(cmd.iter());
         clickwriteln!(writer, "Starting on {} in terminal", pod.name());
-        duct::cmd(targs[0], &targs[1..]).start()?;
-        Ok(())
+        kubectl.spawn_in_terminal(terminal)
     } else {
-        let mut command = Command::new(kubectl_binary);
-        command
-            .arg("--namespace")
-            .arg(ns)
-            .arg("--context")
-            .arg(kluster_name)
-            .arg("exec");
-        if let Some(it) = it_arg {
-            command.arg(it);
-        }
-        command.arg(pod.name());
-        if let Some(user) = env.get_impersonate_user() {
-            command.arg("--as").arg(user);
-        }
-        if let Some(cont) = cont_opt {
-            command.arg("-c").arg(cont).arg("--").args(cmd);
-        } else {
-            command.arg("--").args(cmd);
-        };
-        match command.status() {
-            Ok(s) => {
-                if s.success() {
-                    Ok(())
-                } else {
-                    Err(ClickError::CommandError(
-                        "kubectl exited abnormally".to_string(),
-                    ))
-                }
-            }
-            Err(e) => {
-                if let io::ErrorKind::NotFound = e.kind() {
-                    let msg = if kubectl_binary.starts_with('/') {
-                        format!("Could not find kubectl binary: '{kubectl_binary}'. Does it exist?")
-                    } else {
-                        format!(
-                            "Could not find kubectl binary: '{kubectl_binary}'. Is it in your PATH?"
-                        )
-                    };
-                    Err(ClickError::CommandError(msg))
-                } else {
-                    Err(ClickError::Io(e))
-                }
-            }
-        }
+        kubectl.run()
     }
 }
 
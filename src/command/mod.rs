@@ -0,0 +1,57 @@
+// Copyright 2021 Databricks, Inc.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+pub mod attach;
+pub mod command_def;
+pub mod command_exec;
+pub mod completions;
+pub mod exec;
+pub mod plugin;
+pub mod port_forward;
+
+use crate::command::command_def::Cmd;
+
+use std::path::Path;
+use std::sync::OnceLock;
+
+static PLUGIN_CACHE: OnceLock<Vec<plugin::PluginInfo>> = OnceLock::new();
+
+/// Build the table of commands available in the REPL: the built-in commands
+/// first, followed by one dynamic command per plugin found in `plugin_dir`,
+/// if any. Plugin discovery (spawning every plugin binary to ask for its
+/// signature) only happens once per process no matter how many times this
+/// is called, since `command_vec` is called on every `completions`
+/// invocation in addition to REPL startup.
+///
+/// `plugin_dir` is threaded in by the caller rather than read off of
+/// `Env` directly, since this series doesn't add a plugin directory setting
+/// to `ClickConfig` - the REPL startup code that owns that config is outside
+/// this diff.
+pub fn command_vec(plugin_dir: Option<&Path>) -> Vec<Box<dyn Cmd>> {
+    let mut commands: Vec<Box<dyn Cmd>> = vec![
+        Box::new(exec::Exec::new()),
+        Box::new(attach::Attach::new()),
+        Box::new(port_forward::PortForward::new()),
+        Box::new(completions::Completions::new()),
+    ];
+
+    if let Some(dir) = plugin_dir {
+        let infos = PLUGIN_CACHE.get_or_init(|| plugin::discover_plugins(dir));
+        for info in infos {
+            commands.push(Box::new(plugin::PluginCmd::new(info.clone())));
+        }
+    }
+
+    commands
+}
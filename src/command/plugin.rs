@@ -0,0 +1,303 @@
+// Copyright 2021 Databricks, Inc.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Support for out-of-process plugins that register new top level commands.
+//!
+//! A plugin is just an executable.  On startup we spawn it, ask it (over a
+//! line-delimited JSON-RPC 2.0 connection on its stdin/stdout) what command it
+//! wants to register, and build a [`clap::Command`] from the answer.  When the
+//! user actually runs the command we spawn the plugin again and forward the
+//! selected objects and parsed arguments to it, writing back whatever rows it
+//! returns.
+
+use clap::{Arg, Command as ClapCommand, ArgMatches};
+use rustyline::completion::Pair as RustlinePair;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    command::command_def::Cmd, env::Env, error::ClickError, output::ClickWriter,
+};
+
+use std::cell::RefCell;
+use std::path::{Path, PathBuf};
+
+/// Collects the objects in the current selection as JSON, for handing to an
+/// external plugin in one batch. Built entirely on top of the existing
+/// `apply_to_selection` rather than a bespoke selection accessor, since that's
+/// the only way the rest of the command modules read the current selection.
+fn selected_objects_json(
+    env: &Env,
+    writer: &mut ClickWriter,
+) -> Result<Vec<serde_json::Value>, ClickError> {
+    let selected: RefCell<Vec<serde_json::Value>> = RefCell::new(Vec::new());
+    env.apply_to_selection(
+        writer,
+        Some(&env.click_config.range_separator),
+        |obj, _writer| {
+            selected
+                .borrow_mut()
+                .push(serde_json::to_value(obj).unwrap_or(serde_json::Value::Null));
+            Ok(())
+        },
+    )?;
+    Ok(selected.into_inner())
+}
+
+#[derive(Debug, Serialize)]
+struct RpcRequest<'a, T: Serialize> {
+    jsonrpc: &'a str,
+    method: &'a str,
+    params: T,
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcResponse<T> {
+    #[allow(dead_code)]
+    jsonrpc: String,
+    result: Option<T>,
+    error: Option<RpcError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcError {
+    message: String,
+}
+
+/// One argument a plugin wants to accept, as described by its `config` reply.
+#[derive(Debug, Clone, Deserialize)]
+pub struct PluginArgSpec {
+    pub name: String,
+    pub help: String,
+    #[serde(default)]
+    pub required: bool,
+    #[serde(default)]
+    pub takes_value: bool,
+}
+
+/// The signature a plugin returns in response to a `config` call, describing
+/// the command Click should register on its behalf.
+#[derive(Debug, Clone, Deserialize)]
+pub struct PluginSignature {
+    pub name: String,
+    pub help: String,
+    #[serde(default)]
+    pub args: Vec<PluginArgSpec>,
+}
+
+/// A plugin binary found in the configured plugin directory, along with the
+/// signature it returned for its `config` call.
+#[derive(Debug, Clone)]
+pub struct PluginInfo {
+    pub path: PathBuf,
+    pub signature: PluginSignature,
+}
+
+fn send_request<T: Serialize, R: for<'de> Deserialize<'de>>(
+    binary: &Path,
+    method: &str,
+    params: T,
+) -> Result<R, ClickError> {
+    let request = RpcRequest {
+        jsonrpc: "2.0",
+        method,
+        params,
+    };
+    let mut line = serde_json::to_string(&request)?;
+    line.push('\n');
+
+    // Feed the request over stdin and capture stdout via duct, which writes
+    // stdin from a background thread so a plugin that produces a lot of
+    // output before it finishes reading its request can't deadlock against
+    // us writing that request synchronously.
+    let output = duct::cmd(binary, Vec::<&str>::new())
+        .stdin_bytes(line)
+        .stdout_capture()
+        .unchecked()
+        .run()
+        .map_err(|e| {
+            ClickError::CommandError(format!(
+                "Could not start plugin '{}': {e}",
+                binary.display()
+            ))
+        })?;
+
+    if !output.status.success() {
+        return Err(ClickError::CommandError(format!(
+            "Plugin '{}' exited abnormally",
+            binary.display()
+        )));
+    }
+
+    let reply = String::from_utf8_lossy(&output.stdout);
+    let reply_line = reply.lines().next().unwrap_or("");
+    parse_rpc_reply(reply_line)
+}
+
+/// Decode one line of a plugin's JSON-RPC reply, surfacing both malformed
+/// JSON and a well-formed `error` response as a `ClickError`.
+fn parse_rpc_reply<R: for<'de> Deserialize<'de>>(reply_line: &str) -> Result<R, ClickError> {
+    let response: RpcResponse<R> = serde_json::from_str(reply_line.trim())
+        .map_err(|e| ClickError::CommandError(format!("Invalid response from plugin: {e}")))?;
+    if let Some(err) = response.error {
+        return Err(ClickError::CommandError(err.message));
+    }
+    response
+        .result
+        .ok_or_else(|| ClickError::CommandError("Plugin returned no result".to_string()))
+}
+
+/// Scan `dir` for executable plugins, asking each one for its signature.
+/// Plugins that fail to answer are skipped rather than aborting startup.
+pub fn discover_plugins(dir: &Path) -> Vec<PluginInfo> {
+    let mut plugins = Vec::new();
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return plugins,
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !path.is_file() {
+            continue;
+        }
+        let signature: Result<PluginSignature, ClickError> =
+            send_request(&path, "config", Vec::<()>::new());
+        if let Ok(signature) = signature {
+            plugins.push(PluginInfo { path, signature });
+        }
+    }
+    plugins
+}
+
+fn build_clap_command(signature: &PluginSignature) -> ClapCommand<'static> {
+    let mut clap = ClapCommand::new(Box::leak(signature.name.clone().into_boxed_str()) as &'static str)
+        .about(Box::leak(signature.help.clone().into_boxed_str()) as &'static str);
+    for arg in &signature.args {
+        let name = Box::leak(arg.name.clone().into_boxed_str()) as &'static str;
+        let help = Box::leak(arg.help.clone().into_boxed_str()) as &'static str;
+        clap = clap.arg(
+            Arg::new(name)
+                .long(name)
+                .help(help)
+                .required(arg.required)
+                .takes_value(arg.takes_value),
+        );
+    }
+    clap
+}
+
+/// A dynamically registered command backed by an external plugin binary.
+pub struct PluginCmd {
+    info: PluginInfo,
+    clap: ClapCommand<'static>,
+}
+
+impl PluginCmd {
+    pub fn new(info: PluginInfo) -> PluginCmd {
+        let clap = build_clap_command(&info.signature);
+        PluginCmd { info, clap }
+    }
+}
+
+impl Cmd for PluginCmd {
+    fn name(&self) -> &str {
+        &self.info.signature.name
+    }
+
+    fn aliases(&self) -> Vec<&str> {
+        vec![]
+    }
+
+    fn about(&self) -> &str {
+        &self.info.signature.help
+    }
+
+    fn complete(&self, _prefix: &str, _env: &Env) -> Vec<RustlinePair> {
+        noop_complete!()
+    }
+
+    fn clap_command(&self) -> ClapCommand<'static> {
+        self.clap.clone()
+    }
+
+    fn exec(&self, env: &mut Env, matches: &ArgMatches, writer: &mut ClickWriter) -> Result<(), ClickError> {
+        let selected = selected_objects_json(env, writer)?;
+        let parsed_args: serde_json::Map<String, serde_json::Value> = self
+            .info
+            .signature
+            .args
+            .iter()
+            .filter_map(|arg| {
+                matches
+                    .get_one::<String>(&arg.name)
+                    .map(|v| (arg.name.clone(), serde_json::Value::String(v.clone())))
+            })
+            .collect();
+
+        let rows: Vec<String> = send_request(
+            &self.info.path,
+            &self.info.signature.name,
+            (selected, serde_json::Value::Object(parsed_args)),
+        )?;
+        for row in rows {
+            clickwriteln!(writer, "{}", row);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_rpc_reply_surfaces_jsonrpc_error() {
+        let reply = r#"{"jsonrpc":"2.0","error":{"message":"boom"}}"#;
+        let result: Result<Vec<String>, ClickError> = parse_rpc_reply(reply);
+        match result {
+            Err(ClickError::CommandError(msg)) => assert_eq!(msg, "boom"),
+            other => panic!("expected CommandError, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn parse_rpc_reply_rejects_malformed_json() {
+        let result: Result<Vec<String>, ClickError> = parse_rpc_reply("not json");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn parse_rpc_reply_rejects_missing_result() {
+        let reply = r#"{"jsonrpc":"2.0"}"#;
+        let result: Result<Vec<String>, ClickError> = parse_rpc_reply(reply);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn build_clap_command_includes_name_help_and_args() {
+        let signature = PluginSignature {
+            name: "hello".to_string(),
+            help: "says hello".to_string(),
+            args: vec![PluginArgSpec {
+                name: "loud".to_string(),
+                help: "shout it".to_string(),
+                required: false,
+                takes_value: false,
+            }],
+        };
+        let clap = build_clap_command(&signature);
+        assert_eq!(clap.get_name(), "hello");
+        assert_eq!(clap.get_about(), Some("says hello"));
+        assert!(clap.get_arguments().any(|arg| arg.get_id() == "loud"));
+    }
+}
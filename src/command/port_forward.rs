@@ -0,0 +1,131 @@
+// Copyright 2021 Databricks, Inc.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use clap::{Arg, Command as ClapCommand};
+
+use crate::{
+    command::command_def::{exec_match, start_clap, Cmd},
+    command::command_exec::KubectlCommand,
+    env::Env,
+    error::ClickError,
+    kobj::KObj,
+    output::ClickWriter,
+};
+
+use std::collections::HashMap;
+use std::io::Write;
+
+#[allow(clippy::too_many_arguments)]
+fn do_port_forward(
+    env: &Env,
+    pod: &KObj,
+    kluster_name: &str,
+    ports: &[&str],
+    address_opt: &Option<&str>,
+    term_opt: &Option<&str>,
+    do_terminal: bool,
+    writer: &mut ClickWriter,
+) -> Result<(), ClickError> {
+    let ns = pod.namespace.as_ref().unwrap();
+    let mut kubectl = KubectlCommand::new(env, ns, kluster_name, "port-forward").arg(pod.name());
+    if let Some(address) = address_opt {
+        kubectl = kubectl.arg("--address").arg(address);
+    }
+    kubectl = kubectl.args(ports.iter().copied());
+
+    if do_terminal {
+        let terminal = if let Some(t) = term_opt {
+            t
+        } else if let Some(ref t) = env.click_config.terminal {
+            t
+        } else {
+            "xterm -e"
+        };
+        clickwriteln!(writer, "Forwarding on {} in terminal", pod.name());
+        kubectl.spawn_in_terminal(terminal)
+    } else {
+        kubectl.run()
+    }
+}
+
+command!(
+    PortForward,
+    "port-forward",
+    "forward one or more local ports to a pod",
+    |clap: ClapCommand<'static>| clap
+        .arg(
+            Arg::new("ports")
+                .help("The ports to forward, either PORT or LOCAL_PORT:REMOTE_PORT")
+                .required(true)
+                .multiple_values(true)
+                .index(1)
+        )
+        .arg(
+            Arg::new("address")
+                .long("address")
+                .help("Addresses to listen on, comma separated")
+                .takes_value(true)
+        )
+        .arg(
+            Arg::new("terminal")
+                .short('t')
+                .long("terminal")
+                .help(
+                    "Run port-forward in a new terminal.  With --terminal ARG, ARG is used as \
+                     the terminal command, otherwise the default is used ('set terminal <value>' \
+                     to specify default). If a range of objects is selected, a new terminal is \
+                     opened for each object."
+                )
+                .takes_value(true)
+                .min_values(0)
+        ),
+    vec!["port-forward"],
+    noop_complete!(),
+    HashMap::new(),
+    |matches, env, writer| {
+        let context = env.context.as_ref().ok_or_else(|| {
+            ClickError::CommandError(
+                "Need an active context in order to port-forward.".to_string(),
+            )
+        })?;
+        let ports: Vec<&str> = matches
+            .get_many::<String>("ports")
+            .unwrap()
+            .map(|s| s.as_str())
+            .collect(); // safe as required
+        env.apply_to_selection(
+            writer,
+            Some(&env.click_config.range_separator),
+            |obj, writer| {
+                if obj.is_pod() {
+                    do_port_forward(
+                        env,
+                        obj,
+                        &context.name,
+                        &ports,
+                        &matches.get_one::<String>("address").map(|s| s.as_str()),
+                        &matches.get_one::<String>("terminal").map(|s| s.as_str()),
+                        matches.contains_id("terminal"),
+                        writer,
+                    )
+                } else {
+                    Err(ClickError::CommandError(
+                        "Port-forward only possible on pods".to_string(),
+                    ))
+                }
+            },
+        )
+    },
+    false
+);